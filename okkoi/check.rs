@@ -1,13 +1,16 @@
 use std::{
 	sync::{
-		atomic::{AtomicBool, Ordering},
+		atomic::{AtomicBool, AtomicU32, Ordering},
 		Mutex,
 	},
 	thread,
 	time::Duration,
 };
 
-use crate::{ActorUnit, Unit};
+use crate::{
+	ActorUnit, AsyncActorUnit, AsyncUnit, Blueprint, CycleError, ReleaseError, RestartLimit,
+	RestartPolicy, Unit,
+};
 
 // =========================================================================
 
@@ -54,9 +57,9 @@ fn example_start() {
 struct RaceWithoutSetupActor;
 
 impl super::Actor for RaceWithoutSetupActor {
-	unsafe fn spawn(
-		f: extern "C" fn(*const crate::ActorUnit<Self>),
-		s: &'static crate::ActorUnit<Self>,
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
 	) {
 		thread::spawn(move || f(s));
 	}
@@ -90,9 +93,9 @@ fn check_race_without_setup() {
 struct NoRaceWithSetupActor;
 
 impl super::Actor for NoRaceWithSetupActor {
-	unsafe fn spawn(
-		f: extern "C" fn(*const crate::ActorUnit<Self>),
-		s: &'static crate::ActorUnit<Self>,
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
 	) {
 		thread::spawn(move || f(s));
 	}
@@ -123,3 +126,345 @@ fn check_no_race_with_setup() {
 	assert!(!CANNOT_STOP.load(Ordering::Relaxed));
 	drop(lock);
 }
+
+// =========================================================================
+
+// `start` returns immediately every time, forcing a restart on each run
+// until `RESTART_LIMIT` is exhausted and the unit gives up.
+static RESTART_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+struct RestartingActor;
+
+impl super::Actor for RestartingActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn start(&self) {}
+
+	fn abort(&self) {}
+
+	const RESTART_POLICY: RestartPolicy = RestartPolicy::Always;
+
+	const RESTART_LIMIT: RestartLimit = RestartLimit {
+		max_restarts: 2,
+		within: Duration::from_secs(60),
+	};
+
+	fn on_restart(&self, attempt: u32) {
+		RESTART_ATTEMPTS.store(attempt, Ordering::Relaxed);
+	}
+}
+
+static RESTARTING_ACTOR_UNIT: ActorUnit<RestartingActor> = ActorUnit::new(RestartingActor);
+
+#[test]
+fn check_restart_limit_gives_up() {
+	let lock = SEMAPHORE.lock().unwrap();
+	RESTART_ATTEMPTS.store(0, Ordering::Relaxed);
+	unsafe { RESTARTING_ACTOR_UNIT.acquire() };
+	// `start` returns instantly, so the restart storm runs to completion
+	// (and the unit settles into `Failed`) well within this sleep; avoids
+	// racing `try_release` against an in-progress restart.
+	thread::sleep(Duration::from_millis(100));
+	let result = unsafe { RESTARTING_ACTOR_UNIT.try_release() };
+	assert!(result.is_ok());
+	assert_eq!(RESTART_ATTEMPTS.load(Ordering::Relaxed), 2);
+	drop(lock);
+}
+
+// =========================================================================
+
+// `abort` ignores the request entirely, so `abort_timeout` always elapses
+// and `force_kill` always runs. `HANG_SHOULD_STOP` is a side channel the
+// real `abort()` would never use, letting the test end the background
+// thread cleanly once it is done asserting on the timeout.
+static HANG_SHOULD_STOP: AtomicBool = AtomicBool::new(false);
+static FORCE_KILLED: AtomicBool = AtomicBool::new(false);
+
+struct HangingActor;
+
+impl super::Actor for HangingActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn start(&self) {
+		while !HANG_SHOULD_STOP.load(Ordering::Relaxed) {
+			thread::sleep(Duration::from_millis(5));
+		}
+	}
+
+	fn abort(&self) {
+		// Deliberately does not honor the request.
+	}
+
+	fn abort_timeout(&self) -> Option<Duration> {
+		Some(Duration::from_millis(50))
+	}
+
+	fn force_kill(&self) {
+		FORCE_KILLED.store(true, Ordering::Relaxed);
+	}
+}
+
+static HANGING_ACTOR_UNIT: ActorUnit<HangingActor> = ActorUnit::new(HangingActor);
+
+#[test]
+fn check_abort_timeout_force_kill() {
+	let lock = SEMAPHORE.lock().unwrap();
+	HANG_SHOULD_STOP.store(false, Ordering::Relaxed);
+	FORCE_KILLED.store(false, Ordering::Relaxed);
+
+	unsafe { HANGING_ACTOR_UNIT.acquire() };
+	let result = unsafe { HANGING_ACTOR_UNIT.try_release() };
+	assert!(matches!(result, Err(ReleaseError::Timeout)));
+	assert!(FORCE_KILLED.load(Ordering::Relaxed));
+
+	// The unit is now `Killed` while its thread runs: reacquiring must refuse rather
+	// than risk a second thread racing the one `force_kill` couldn't stop.
+	let reacquired = std::panic::catch_unwind(|| unsafe { HANGING_ACTOR_UNIT.acquire() });
+	assert!(reacquired.is_err());
+
+	// Let the still-running background thread actually exit so it
+	// doesn't linger into later tests.
+	HANG_SHOULD_STOP.store(true, Ordering::Relaxed);
+	thread::sleep(Duration::from_millis(50));
+	drop(lock);
+}
+
+// =========================================================================
+
+// A diamond: ROOT depends on both BRANCH_A and BRANCH_B, which both
+// depend on LEAF. Acquiring/releasing ROOT must only start/stop LEAF
+// once, not twice.
+static LEAF_ACQUIRES: AtomicU32 = AtomicU32::new(0);
+static LEAF_RELEASES: AtomicU32 = AtomicU32::new(0);
+static LEAF_STOP: AtomicBool = AtomicBool::new(false);
+
+struct LeafActor;
+
+impl super::Actor for LeafActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn setup(&self) {
+		LEAF_ACQUIRES.fetch_add(1, Ordering::Relaxed);
+	}
+
+	fn start(&self) {
+		while !LEAF_STOP.load(Ordering::Relaxed) {
+			thread::sleep(Duration::from_millis(5));
+		}
+	}
+
+	fn abort(&self) {
+		LEAF_RELEASES.fetch_add(1, Ordering::Relaxed);
+		LEAF_STOP.store(true, Ordering::Relaxed);
+	}
+}
+
+static LEAF_UNIT: ActorUnit<LeafActor> = ActorUnit::new(LeafActor);
+
+static BRANCH_A_STOP: AtomicBool = AtomicBool::new(false);
+
+struct BranchAActor;
+
+impl super::Actor for BranchAActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn start(&self) {
+		while !BRANCH_A_STOP.load(Ordering::Relaxed) {
+			thread::sleep(Duration::from_millis(5));
+		}
+	}
+
+	fn abort(&self) {
+		BRANCH_A_STOP.store(true, Ordering::Relaxed);
+	}
+}
+
+static BRANCH_B_STOP: AtomicBool = AtomicBool::new(false);
+
+struct BranchBActor;
+
+impl super::Actor for BranchBActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn start(&self) {
+		while !BRANCH_B_STOP.load(Ordering::Relaxed) {
+			thread::sleep(Duration::from_millis(5));
+		}
+	}
+
+	fn abort(&self) {
+		BRANCH_B_STOP.store(true, Ordering::Relaxed);
+	}
+}
+
+static ROOT_STOP: AtomicBool = AtomicBool::new(false);
+
+struct RootActor;
+
+impl super::Actor for RootActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn start(&self) {
+		while !ROOT_STOP.load(Ordering::Relaxed) {
+			thread::sleep(Duration::from_millis(5));
+		}
+	}
+
+	fn abort(&self) {
+		ROOT_STOP.store(true, Ordering::Relaxed);
+	}
+}
+
+static BRANCH_A_UNIT: ActorUnit<BranchAActor> = ActorUnit::with_deps(BranchAActor, &[&LEAF_UNIT]);
+static BRANCH_B_UNIT: ActorUnit<BranchBActor> = ActorUnit::with_deps(BranchBActor, &[&LEAF_UNIT]);
+static ROOT_UNIT: ActorUnit<RootActor> =
+	ActorUnit::with_deps(RootActor, &[&BRANCH_A_UNIT, &BRANCH_B_UNIT]);
+
+static DIAMOND_BLUEPRINT: Blueprint<&ActorUnit<RootActor>> = Blueprint::new(&ROOT_UNIT);
+
+#[test]
+fn check_diamond_deps_refcount_and_blueprint_validate() {
+	let lock = SEMAPHORE.lock().unwrap();
+	LEAF_ACQUIRES.store(0, Ordering::Relaxed);
+	LEAF_RELEASES.store(0, Ordering::Relaxed);
+	LEAF_STOP.store(false, Ordering::Relaxed);
+	BRANCH_A_STOP.store(false, Ordering::Relaxed);
+	BRANCH_B_STOP.store(false, Ordering::Relaxed);
+	ROOT_STOP.store(false, Ordering::Relaxed);
+
+	assert!(DIAMOND_BLUEPRINT.validate().is_ok());
+
+	unsafe { ROOT_UNIT.acquire() };
+	// Both branches acquired LEAF, but it must have only spawned once.
+	assert_eq!(LEAF_ACQUIRES.load(Ordering::Relaxed), 1);
+
+	unsafe { ROOT_UNIT.release() };
+	// Only the last of the two branch releases should have torn LEAF down.
+	assert_eq!(LEAF_RELEASES.load(Ordering::Relaxed), 1);
+	drop(lock);
+}
+
+// Two units that declare each other as dependencies: validating a
+// blueprint rooted at either one must report the cycle instead of
+// recursing forever.
+struct CycleAActor;
+
+impl super::Actor for CycleAActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn start(&self) {}
+	fn abort(&self) {}
+}
+
+struct CycleBActor;
+
+impl super::Actor for CycleBActor {
+	unsafe fn spawn<R1: crate::RelaxStrategy>(
+		f: extern "C" fn(*const crate::ActorUnit<Self, R1>),
+		s: &'static crate::ActorUnit<Self, R1>,
+	) {
+		thread::spawn(move || f(s));
+	}
+
+	fn start(&self) {}
+	fn abort(&self) {}
+}
+
+static CYCLE_B_UNIT: ActorUnit<CycleBActor> = ActorUnit::with_deps(CycleBActor, &[&CYCLE_A_UNIT]);
+static CYCLE_A_UNIT: ActorUnit<CycleAActor> = ActorUnit::with_deps(CycleAActor, &[&CYCLE_B_UNIT]);
+
+static CYCLE_BLUEPRINT: Blueprint<&ActorUnit<CycleAActor>> = Blueprint::new(&CYCLE_A_UNIT);
+
+#[test]
+fn check_blueprint_validate_detects_cycle() {
+	let lock = SEMAPHORE.lock().unwrap();
+	assert!(matches!(CYCLE_BLUEPRINT.validate(), Err(CycleError)));
+	drop(lock);
+}
+
+// =========================================================================
+
+static ASYNC_STARTS: AtomicU32 = AtomicU32::new(0);
+static ASYNC_ABORTS: AtomicU32 = AtomicU32::new(0);
+static ASYNC_SHOULD_STOP: AtomicBool = AtomicBool::new(false);
+
+struct AsyncActorImpl;
+
+impl super::AsyncActor for AsyncActorImpl {
+	fn spawn(handle: &tokio::runtime::Handle, unit: &'static AsyncActorUnit<Self>) {
+		handle.spawn(unit.run());
+	}
+
+	async fn start(&self) {
+		ASYNC_STARTS.fetch_add(1, Ordering::Relaxed);
+		while !ASYNC_SHOULD_STOP.load(Ordering::Relaxed) {
+			tokio::task::yield_now().await;
+		}
+	}
+
+	async fn abort(&self) {
+		ASYNC_ABORTS.fetch_add(1, Ordering::Relaxed);
+		ASYNC_SHOULD_STOP.store(true, Ordering::Relaxed);
+	}
+}
+
+static ASYNC_ACTOR_UNIT: AsyncActorUnit<AsyncActorImpl> = AsyncActorUnit::new(AsyncActorImpl);
+
+#[test]
+fn check_async_unit_acquire_release_refcount() {
+	let lock = SEMAPHORE.lock().unwrap();
+	ASYNC_STARTS.store(0, Ordering::Relaxed);
+	ASYNC_ABORTS.store(0, Ordering::Relaxed);
+	ASYNC_SHOULD_STOP.store(false, Ordering::Relaxed);
+
+	tokio::runtime::Runtime::new().unwrap().block_on(async {
+		unsafe { ASYNC_ACTOR_UNIT.acquire().await };
+		unsafe { ASYNC_ACTOR_UNIT.acquire().await };
+		// A second acquire while already running must not spawn again.
+		assert_eq!(ASYNC_STARTS.load(Ordering::Relaxed), 1);
+
+		unsafe { ASYNC_ACTOR_UNIT.release().await };
+		// Dropping one of two references must not abort the actor yet.
+		assert_eq!(ASYNC_ABORTS.load(Ordering::Relaxed), 0);
+
+		unsafe { ASYNC_ACTOR_UNIT.release().await };
+		// Only the last release should have torn the actor down.
+		assert_eq!(ASYNC_ABORTS.load(Ordering::Relaxed), 1);
+	});
+	drop(lock);
+}
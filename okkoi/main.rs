@@ -1,28 +1,186 @@
 use core::{
+	hint,
+	marker::PhantomData,
 	mem::ManuallyDrop,
-	sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+	sync::atomic::{AtomicUsize, Ordering},
 };
-use std::{sync::Mutex, thread};
+use std::{
+	future::Future,
+	panic::{self, AssertUnwindSafe},
+	sync::{Condvar, Mutex},
+	thread,
+	time::{Duration, Instant},
+	vec::Vec,
+};
+use tokio::{
+	runtime::Handle,
+	sync::{Mutex as AsyncMutex, Notify},
+};
+
+#[cfg(test)]
+mod check;
 
 // =========================================================================
 
+/// Strategy for the short spin phase `ActorUnit` runs before parking on
+/// its condvar while waiting for a state transition.
+pub trait RelaxStrategy: Default + Send + Sync {
+	fn relax(&mut self);
+}
+
+/// Pure busy-spin using the architecture's spin-loop hint. Best when the
+/// wait is expected to be extremely short (a handful of instructions).
+#[derive(Default)]
+pub struct Spin;
+
+impl RelaxStrategy for Spin {
+	fn relax(&mut self) {
+		hint::spin_loop();
+	}
+}
+
+/// Yield to the scheduler every iteration. The default: cheap and fair
+/// when the spin phase isn't expected to pay off every time.
+#[derive(Default)]
+pub struct Yield;
+
+impl RelaxStrategy for Yield {
+	fn relax(&mut self) {
+		thread::yield_now();
+	}
+}
+
+/// Exponential backoff: spin with a growing number of hints, then fall
+/// back to yielding once the wait has gone on long enough that spinning
+/// stops paying off.
+#[derive(Default)]
+pub struct Backoff {
+	step: u32,
+}
+
+impl Backoff {
+	// Once step reaches this, spinning 1 << step hints is no longer worth
+	// it and we switch to yielding instead.
+	const YIELD_THRESHOLD: u32 = 6;
+	// Cap the shift so step can keep growing past the threshold without
+	// ever overflowing.
+	const MAX_STEP: u32 = 10;
+}
+
+impl RelaxStrategy for Backoff {
+	fn relax(&mut self) {
+		if self.step < Self::YIELD_THRESHOLD {
+			for _ in 0..1u32 << self.step {
+				hint::spin_loop();
+			}
+		} else {
+			thread::yield_now();
+		}
+		self.step = (self.step + 1).min(Self::MAX_STEP);
+	}
+}
+
+// =========================================================================
+
+/// How an `ActorUnit` should react when its actor's `start()` returns (or
+/// panics) while it is still needed by dependents.
+pub enum RestartPolicy {
+	/// Propagate the exit as a panic, same as the unit always used to.
+	Never,
+	/// Restart regardless of whether `start()` panicked or just returned.
+	Always,
+	/// Restart only if `start()` panicked; a plain early return is still
+	/// treated as unrecoverable, same as `Never`.
+	OnFailure,
+}
+
+/// Sliding-window budget for restarts: at most `max_restarts` within the
+/// last `within` of wall-clock time, after which the unit gives up.
+pub struct RestartLimit {
+	pub max_restarts: u32,
+	pub within: Duration,
+}
+
 pub trait Actor
 where
 	Self: Sized,
 {
-	unsafe fn spawn(
-		f: extern "C" fn(*const ActorUnit<Self>),
-		s: &'static ActorUnit<Self>,
+	unsafe fn spawn<R1: RelaxStrategy>(
+		f: extern "C" fn(*const ActorUnit<Self, R1>),
+		s: &'static ActorUnit<Self, R1>,
 	);
+	/// Runs before the unit is reported as running, and again before
+	/// every restart. Dependents
+	/// unblocked by `acquire` are guaranteed to observe whatever state
+	/// `setup` establishes.
+	fn setup(&self) {}
 	fn start(&self);
 	fn abort(&self);
+
+	/// Defaults to the legacy behavior: any unexpected exit is fatal.
+	const RESTART_POLICY: RestartPolicy = RestartPolicy::Never;
+
+	/// Restart budget consulted when `RESTART_POLICY` allows restarting.
+	const RESTART_LIMIT: RestartLimit = RestartLimit {
+		max_restarts: 0,
+		within: Duration::ZERO,
+	};
+
+	/// Called right before `setup`/`start` are re-run after a restart, so
+	/// implementors can reset whatever state the previous run left dirty.
+	/// `attempt` counts restarts from 1.
+	fn on_restart(&self, attempt: u32) {
+		let _ = attempt;
+	}
+
+	/// Upper bound on how long `release`/`try_release` will wait for
+	/// `abort()` to be honored before giving up. `None` waits forever,
+	/// same as the unit always used to.
+	fn abort_timeout(&self) -> Option<Duration> {
+		None
+	}
+
+	/// Called once `abort_timeout()` elapses without the actor stopping.
+	/// Implementors should use this to forcibly terminate the actor
+	/// thread if at all possible; the default does nothing, leaving the
+	/// thread running in the background. Either way, the unit is moved
+	/// to a "killed" state and refuses to be re-acquired, since the
+	/// original thread is not guaranteed to have exited. It leaves that
+	/// state only if the original thread's `start()` does return later,
+	/// which moves the unit back to idle.
+	fn force_kill(&self) {}
+}
+
+/// Error returned by [`ActorUnit::try_release`].
+#[derive(Debug)]
+pub enum ReleaseError {
+	/// The actor did not honor `abort()` within its `abort_timeout()`.
+	Timeout,
 }
 
-pub trait Unit {
+/// `Send + Sync` so that `&'static dyn Unit` (used in `deps`) is itself
+/// `Sync`, which `static ActorUnit<...>` declarations require.
+pub trait Unit: Send + Sync {
 	/// SAFETY: Acquire must be called as many times as release.
 	unsafe fn acquire(&'static self);
 	/// SAFETY: Release must be called as many times as acquire.
 	unsafe fn release(&'static self);
+
+	/// Units this one depends on. `acquire` brings these up first,
+	/// `release` tears them down last. Empty by default.
+	fn deps(&'static self) -> &'static [&'static dyn Unit] {
+		&[]
+	}
+
+	/// Pushes this unit's graph nodes into `into`. Plumbing wrappers like
+	/// `(U1, U2)` and `Blueprint` push their constituents instead of
+	/// themselves, so walking a `Blueprint` visits real nodes only.
+	fn collect_roots(&'static self, into: &mut Vec<&'static dyn Unit>)
+	where
+		Self: Sized + 'static,
+	{
+		into.push(self);
+	}
 }
 
 impl<T1: Unit> Unit for &T1 {
@@ -33,16 +191,77 @@ impl<T1: Unit> Unit for &T1 {
 	unsafe fn release(&'static self) {
 		T1::release(self);
 	}
+
+	fn deps(&'static self) -> &'static [&'static dyn Unit] {
+		T1::deps(self)
+	}
+
+	fn collect_roots(&'static self, into: &mut Vec<&'static dyn Unit>)
+	where
+		Self: Sized + 'static,
+	{
+		T1::collect_roots(self, into);
+	}
 }
 
+/// A dependency cycle was found while validating a [`Blueprint`].
+#[derive(Debug)]
+pub struct CycleError;
+
 // =========================================================================
 
-pub struct ActorUnit<A1>
+// State of the underlying actor thread, driven by `ActorUnit::start` and
+// watched through `condvar`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+	Idle,
+	Starting,
+	Running,
+	Stopping,
+	// Restart budget exhausted; stays so until the next `acquire`,
+	// which spawns the actor again whatever the current count.
+	Failed,
+	// `abort_timeout()` elapsed and `force_kill()` was called, but that
+	// does not guarantee the actor thread actually exited. Refuses
+	// re-acquire, since that would risk a second thread racing the
+	// possibly-still-running original over `count`/`state`/`restarts`;
+	// left only when that original thread's `start()` returns (-> Idle).
+	Killed,
+}
+
+// Restart timestamps falling inside the current `RestartLimit::within`
+// window, used to evaluate the sliding window. Unbounded (pruned on every
+// `record`) so `RestartLimit::max_restarts` is enforceable at any size,
+// not just ones that fit some fixed capacity.
+struct RestartHistory {
+	at: Vec<Instant>,
+}
+
+impl RestartHistory {
+	const fn new() -> Self {
+		Self { at: Vec::new() }
+	}
+
+	// Records a restart happening now and returns how many restarts
+	// (this one included) fall within `within` of now.
+	fn record(&mut self, within: Duration) -> u32 {
+		let now = Instant::now();
+		self.at.retain(|at| now.duration_since(*at) <= within);
+		self.at.push(now);
+		self.at.len() as u32
+	}
+}
+
+pub struct ActorUnit<A1, R1 = Yield>
 where
 	A1: Actor,
+	R1: RelaxStrategy,
 {
-	// Flag to check state of actor.
-	running: AtomicBool,
+	// State of actor, guarded by condvar below.
+	state: Mutex<State>,
+
+	// Notified on every state transition.
+	condvar: Condvar,
 
 	// Semaphore to avoid races.
 	semaphore: Mutex<()>,
@@ -50,76 +269,263 @@ where
 	// Count for dependent actors.
 	count: AtomicUsize,
 
+	// Restart timestamps, consulted against the actor's `RESTART_LIMIT`.
+	restarts: Mutex<RestartHistory>,
+
+	// Declared dependencies, acquired before this unit spawns and
+	// released after it aborts.
+	deps: &'static [&'static dyn Unit],
+
 	// Encapsulated actor.
 	actor: A1,
+
+	// Marker so callers can pick the relax strategy for the bounded spin
+	// phase ahead of each condvar wait without storing any state here.
+	relax: PhantomData<R1>,
 }
 
-impl<A1> ActorUnit<A1>
+// Attempts `spin_until` makes before falling back to blocking on the
+// condvar. Bounded so a wedged actor never busy-spins indefinitely.
+const SPIN_ATTEMPTS: u32 = 64;
+
+impl<A1, R1> ActorUnit<A1, R1>
 where
 	A1: Actor,
+	R1: RelaxStrategy,
 {
 	pub const fn new(inner: A1) -> Self {
+		Self::with_deps(inner, &[])
+	}
+
+	pub const fn with_deps(inner: A1, deps: &'static [&'static dyn Unit]) -> Self {
 		Self {
-			running: AtomicBool::new(false),
+			state: Mutex::new(State::Idle),
+			condvar: Condvar::new(),
 			semaphore: Mutex::new(()),
 			count: AtomicUsize::new(0usize),
+			restarts: Mutex::new(RestartHistory::new()),
+			deps,
 			actor: inner,
+			relax: PhantomData,
 		}
 	}
 
-	fn spawn(&'static self) {
-		// SAFETY: Self is borrowed for 'static so pointer will be valid.
-		unsafe { A1::spawn(Self::start, self) };
-		// Yield to scheduler till spawned actor started.
-		while !self.running.load(Ordering::Relaxed) {
-			thread::yield_now();
+	fn set_state(&self, state: State) {
+		*self.state.lock().unwrap() = state;
+		self.condvar.notify_all();
+	}
+
+	// Spins with `R1`'s strategy for up to `SPIN_ATTEMPTS` iterations,
+	// checking `pred` against the current state each time. Returns `true`
+	// as soon as `pred` is satisfied, `false` if the budget ran out.
+	fn spin_until(&self, mut pred: impl FnMut(State) -> bool) -> bool {
+		let mut relax = R1::default();
+		for _ in 0..SPIN_ATTEMPTS {
+			if pred(*self.state.lock().unwrap()) {
+				return true;
+			}
+			relax.relax();
+		}
+		false
+	}
+
+	// Waits until the actor reports itself running, or gives up trying.
+	fn wait_ready(&self) {
+		let ready = |s| matches!(s, State::Running | State::Failed);
+		if self.spin_until(ready) {
+			return;
+		}
+		let guard = self.state.lock().unwrap();
+		drop(self.condvar.wait_while(guard, |s| !ready(*s)).unwrap());
+	}
+
+	// Waits until the actor has fully torn down, however it got there.
+	fn wait_idle(&self) {
+		let idle = |s| matches!(s, State::Idle | State::Failed);
+		if self.spin_until(idle) {
+			return;
+		}
+		let guard = self.state.lock().unwrap();
+		drop(self.condvar.wait_while(guard, |s| !idle(*s)).unwrap());
+	}
+
+	// Same as `wait_idle`, but gives up after `timeout` and reports
+	// whether the actor actually settled in time.
+	fn wait_idle_timeout(&self, timeout: Duration) -> bool {
+		let idle = |s| matches!(s, State::Idle | State::Failed);
+		let start = Instant::now();
+		if self.spin_until(idle) {
+			return true;
 		}
+		let remaining = timeout.saturating_sub(start.elapsed());
+		let guard = self.state.lock().unwrap();
+		let (_guard, result) = self
+			.condvar
+			.wait_timeout_while(guard, remaining, |s| !idle(*s))
+			.unwrap();
+		!result.timed_out()
 	}
 
-	fn running(&self) {
-		self.running.store(true, Ordering::Relaxed);
+	fn spawn(&'static self) {
+		self.set_state(State::Starting);
+		// SAFETY: Self is borrowed for 'static so pointer will be valid.
+		unsafe { A1::spawn(Self::start, self) };
+		self.wait_ready();
 	}
 
 	extern "C" fn start(s: *const Self) {
 		// SAFETY: Assuming Self::spawn called us with right pointer.
 		let this = unsafe { s.as_ref() }.expect("is not null");
-		// Set running and call start that user provided.
-		(this.running(), this.actor.start());
-		// Check counter to know if return is intentional or not.
-		// We do not support non intentional exit yet so panic.
-		if this.count.load(Ordering::Relaxed) != 0 {
-			panic!("actor exited early");
+		let mut attempt = 0u32;
+		loop {
+			if attempt > 0 {
+				this.actor.on_restart(attempt);
+			}
+			// Run setup before reporting running so dependents never
+			// observe a half-initialized actor, then call user's start.
+			this.actor.setup();
+			this.set_state(State::Running);
+			let outcome = panic::catch_unwind(AssertUnwindSafe(|| this.actor.start()));
+
+			// Counter tells us if return is intentional or not.
+			if this.count.load(Ordering::Relaxed) == 0 {
+				this.set_state(State::Idle);
+				if let Err(payload) = outcome {
+					panic::resume_unwind(payload);
+				}
+				return;
+			}
+
+			let restart = match A1::RESTART_POLICY {
+				RestartPolicy::Never => false,
+				RestartPolicy::Always => true,
+				RestartPolicy::OnFailure => outcome.is_err(),
+			};
+			if !restart {
+				if let Err(payload) = outcome {
+					panic::resume_unwind(payload);
+				}
+				panic!("actor exited early");
+			}
+
+			let limit = A1::RESTART_LIMIT;
+			let seen = this.restarts.lock().unwrap().record(limit.within);
+			if seen > limit.max_restarts {
+				this.set_state(State::Failed);
+				return;
+			}
+			attempt += 1;
 		}
-		// We set it after check so that guard is not released.
-		this.running.store(false, Ordering::Relaxed);
 	}
 
-	fn abort(&self) {
-		// Just forward to impl provided by user and then wait.
+	fn abort(&self) -> Result<(), ReleaseError> {
+		{
+			// A unit that already gave up on its own has nothing left
+			// to abort and nobody left to wait for.
+			let mut guard = self.state.lock().unwrap();
+			if *guard == State::Failed {
+				return Ok(());
+			}
+			// Already given up on a previous abort; still stuck.
+			if *guard == State::Killed {
+				return Err(ReleaseError::Timeout);
+			}
+			*guard = State::Stopping;
+			self.condvar.notify_all();
+		}
 		self.actor.abort();
-		// Yield to scheduler till spawned actor stopped.
-		while self.running.load(Ordering::Relaxed) {
-			thread::yield_now();
+		match self.actor.abort_timeout() {
+			None => {
+				self.wait_idle();
+				Ok(())
+			}
+			Some(timeout) if self.wait_idle_timeout(timeout) => Ok(()),
+			Some(_) => {
+				self.actor.force_kill();
+				self.set_state(State::Killed);
+				Err(ReleaseError::Timeout)
+			}
+		}
+	}
+
+	/// Same as [`Unit::release`], but once the actor's `abort_timeout()`
+	/// elapses it reports the stuck actor instead of waiting on it. With
+	/// an `abort_timeout()` of `None` it waits forever, like `release`.
+	///
+	/// SAFETY: Must be called as many times as `acquire`.
+	pub unsafe fn try_release(&'static self) -> Result<(), ReleaseError> {
+		let guard = ManuallyDrop::new(self.semaphore.lock().unwrap());
+		let result = if self.count.fetch_sub(1, Ordering::Relaxed) == 1 {
+			self.abort()
+		} else {
+			Ok(())
+		};
+		drop(ManuallyDrop::into_inner(guard));
+		// If our own abort timed out the actor may still be using a dep;
+		// leave its refcount alone rather than tearing it down early.
+		if result.is_ok() {
+			for dep in self.deps.iter().rev() {
+				unsafe { dep.release() };
+			}
 		}
+		result
 	}
 }
 
-impl<A1> Unit for ActorUnit<A1>
+impl<A1, R1> Unit for ActorUnit<A1, R1>
 where
-	A1: Actor,
+	A1: Actor + Send + Sync,
+	R1: RelaxStrategy,
 {
 	unsafe fn acquire(&'static self) {
+		for dep in self.deps {
+			unsafe { dep.acquire() };
+		}
 		let guard = ManuallyDrop::new(self.semaphore.lock().unwrap());
-		let spawn = || self.spawn();
-		(self.count.fetch_add(1, Ordering::Relaxed) == 0).then(spawn);
+		// Checked (not raced against a concurrent release/abort) because
+		// `release`/`try_release` only move the unit to `Killed` while
+		// holding the semaphore above.
+		if self.count.load(Ordering::Relaxed) == 0 && *self.state.lock().unwrap() == State::Killed {
+			drop(ManuallyDrop::into_inner(guard));
+			panic!(
+				"cannot reacquire a unit whose actor was force-killed after \
+				 abort_timeout elapsed; the previous actor thread is not \
+				 guaranteed to have exited"
+			);
+		}
+		// Unlike `Killed`, `Failed` is only ever set by the actor's own
+		// thread right before it returns for good, so there is no
+		// zombie-thread risk here -- but it can be reached with `count`
+		// still nonzero (other references are still live), so it must be
+		// checked regardless of `count` or a dependent acquiring after
+		// the restart budget was exhausted would silently get a no-op.
+		let failed = *self.state.lock().unwrap() == State::Failed;
+		let first = self.count.fetch_add(1, Ordering::Relaxed) == 0;
+		if first || failed {
+			self.spawn();
+		}
 		drop(ManuallyDrop::into_inner(guard));
 	}
 
 	unsafe fn release(&'static self) {
 		let guard = ManuallyDrop::new(self.semaphore.lock().unwrap());
-		let abort = || self.abort();
-		(self.count.fetch_sub(1, Ordering::Relaxed) == 1).then(abort);
+		let result = if self.count.fetch_sub(1, Ordering::Relaxed) == 1 {
+			self.abort()
+		} else {
+			Ok(())
+		};
 		drop(ManuallyDrop::into_inner(guard));
+		// If our own abort timed out the actor may still be using a dep;
+		// leave its refcount alone rather than tearing it down early.
+		if result.is_ok() {
+			for dep in self.deps.iter().rev() {
+				unsafe { dep.release() };
+			}
+		}
+	}
+
+	fn deps(&'static self) -> &'static [&'static dyn Unit] {
+		self.deps
 	}
 }
 
@@ -139,6 +545,14 @@ where
 		unsafe { self.0.release() };
 		unsafe { self.1.release() };
 	}
+
+	fn collect_roots(&'static self, into: &mut Vec<&'static dyn Unit>)
+	where
+		Self: Sized + 'static,
+	{
+		self.0.collect_roots(into);
+		self.1.collect_roots(into);
+	}
 }
 
 // =========================================================================
@@ -161,6 +575,50 @@ where
 	}
 }
 
+impl<U1> Blueprint<U1>
+where
+	U1: Unit + 'static,
+{
+	/// Walks the dependency edges declared by the registered units (via
+	/// `ActorUnit::with_deps`) and returns an error if any cycle is
+	/// reachable from them.
+	pub fn validate(&'static self) -> Result<(), CycleError> {
+		let mut roots = Vec::new();
+		self.collect_roots(&mut roots);
+
+		let mut visiting = Vec::new();
+		let mut visited = Vec::new();
+		for root in roots {
+			visit(root, &mut visiting, &mut visited)?;
+		}
+		Ok(())
+	}
+}
+
+// DFS with visiting/visited marking: `visiting` holds the current path,
+// `visited` holds nodes already known to be cycle-free. Node identity is
+// the data pointer of the trait object.
+fn visit(
+	unit: &'static dyn Unit,
+	visiting: &mut Vec<*const ()>,
+	visited: &mut Vec<*const ()>,
+) -> Result<(), CycleError> {
+	let id = unit as *const dyn Unit as *const ();
+	if visited.contains(&id) {
+		return Ok(());
+	}
+	if visiting.contains(&id) {
+		return Err(CycleError);
+	}
+	visiting.push(id);
+	for &dep in unit.deps() {
+		visit(dep, visiting, visited)?;
+	}
+	visiting.pop();
+	visited.push(id);
+	Ok(())
+}
+
 impl<U1> Unit for Blueprint<U1>
 where
 	U1: Unit,
@@ -172,4 +630,119 @@ where
 	unsafe fn release(&'static self) {
 		unsafe { self.0.release() };
 	}
+
+	fn collect_roots(&'static self, into: &mut Vec<&'static dyn Unit>)
+	where
+		Self: Sized + 'static,
+	{
+		self.0.collect_roots(into);
+	}
+}
+
+// =========================================================================
+
+/// Async counterpart of [`Actor`]: `spawn` hands the actor's run loop to
+/// a runtime instead of a raw OS thread.
+pub trait AsyncActor
+where
+	Self: Sized,
+{
+	fn spawn(handle: &Handle, unit: &'static AsyncActorUnit<Self>);
+	fn setup(&self) -> impl Future<Output = ()> + Send {
+		async {}
+	}
+	fn start(&self) -> impl Future<Output = ()> + Send;
+	fn abort(&self) -> impl Future<Output = ()> + Send;
+}
+
+/// Async counterpart of [`Unit`].
+pub trait AsyncUnit {
+	/// SAFETY: Acquire must be called as many times as release.
+	unsafe fn acquire(&'static self) -> impl Future<Output = ()> + Send;
+	/// SAFETY: Release must be called as many times as acquire.
+	unsafe fn release(&'static self) -> impl Future<Output = ()> + Send;
+}
+
+pub struct AsyncActorUnit<A1>
+where
+	A1: AsyncActor,
+{
+	// Notified once the actor reports itself running.
+	ready: Notify,
+
+	// Notified once the actor reports itself idle again.
+	idle: Notify,
+
+	// Async semaphore to avoid races, so waiting for it never blocks an
+	// executor thread.
+	semaphore: AsyncMutex<()>,
+
+	// Count for dependent actors.
+	count: AtomicUsize,
+
+	// Encapsulated actor.
+	actor: A1,
+}
+
+impl<A1> AsyncActorUnit<A1>
+where
+	A1: AsyncActor,
+{
+	pub const fn new(inner: A1) -> Self {
+		Self {
+			ready: Notify::const_new(),
+			idle: Notify::const_new(),
+			semaphore: AsyncMutex::const_new(()),
+			count: AtomicUsize::new(0usize),
+			actor: inner,
+		}
+	}
+
+	/// Entry point for the task `AsyncActor::spawn` hands to the runtime.
+	pub async fn run(&'static self) {
+		// Run setup before reporting running so dependents never observe
+		// a half-initialized actor, then call start that user provided.
+		self.actor.setup().await;
+		self.ready.notify_one();
+		self.actor.start().await;
+		// Check counter to know if return is intentional or not.
+		// We do not support non intentional exit yet so panic.
+		if self.count.load(Ordering::Relaxed) != 0 {
+			panic!("actor exited early");
+		}
+		self.idle.notify_one();
+	}
+
+	async fn spawn(&'static self) {
+		A1::spawn(&Handle::current(), self);
+		// Wait till spawned actor reports itself as running.
+		self.ready.notified().await;
+	}
+
+	async fn abort(&self) {
+		// Just forward to impl provided by user and then wait.
+		self.actor.abort().await;
+		self.idle.notified().await;
+	}
+}
+
+impl<A1> AsyncUnit for AsyncActorUnit<A1>
+where
+	A1: AsyncActor + Sync,
+{
+	async unsafe fn acquire(&'static self) {
+		let guard = self.semaphore.lock().await;
+		if self.count.fetch_add(1, Ordering::Relaxed) == 0 {
+			self.spawn().await;
+		}
+		drop(guard);
+	}
+
+	async unsafe fn release(&'static self) {
+		let guard = self.semaphore.lock().await;
+		if self.count.fetch_sub(1, Ordering::Relaxed) == 1 {
+			self.abort().await;
+		}
+		drop(guard);
+	}
 }